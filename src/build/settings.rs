@@ -4,8 +4,9 @@
 //! in the web application, the categories that will belong to each of them, or
 //! the criteria used to highlight items.
 //!
-//! NOTE: the landscape settings file uses a new format that is not backwards
-//! compatible with the legacy settings file used by existing landscapes.
+//! The legacy settings file format used by existing landscapes is still
+//! supported: it is detected automatically and converted to the new format
+//! before it is used (see `RawLandscapeSettings`).
 
 use super::data::{Category, CategoryName};
 use crate::SettingsSource;
@@ -14,13 +15,23 @@ use lazy_static::lazy_static;
 use regex::Regex;
 use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
-use std::{fs, path::Path};
+use std::{
+    collections::{HashMap, HashSet},
+    env, fs,
+    future::Future,
+    path::{Path, PathBuf},
+    pin::Pin,
+};
 use tracing::{debug, instrument};
 
 /// Landscape settings.
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub(crate) struct LandscapeSettings {
-    pub foundation: String,
+    /// Required, but kept optional here so that a file using `extends` can
+    /// omit it and inherit it from the base file. Enforced in `validate`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub foundation: Option<String>,
+
     pub images: Images,
 
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -29,6 +40,18 @@ pub(crate) struct LandscapeSettings {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub colors: Option<Colors>,
 
+    /// Path or url of a base settings file this file extends. The settings
+    /// defined here take precedence over the ones inherited from the base
+    /// file, which are deep merged in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
+
+    /// Arbitrary extra settings that don't have a dedicated field, usually
+    /// consumed by themes or plugins. They can be read and written using the
+    /// dotted-path accessors below (e.g. `ui.sidebar.collapsed`).
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_yaml::Value>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub featured_items: Option<Vec<FeaturedItemRule>>,
 
@@ -49,32 +72,41 @@ impl LandscapeSettings {
     /// Create a new landscape settings instance from the source provided.
     #[instrument(skip_all, err)]
     pub(crate) async fn new(src: &SettingsSource) -> Result<Self> {
+        Self::new_with_overrides(src, Overrides::default()).await
+    }
+
+    /// Same as `new`, but also accepts explicit overrides (e.g. built from
+    /// CLI flags by the caller) to apply on top of the ones picked up from
+    /// the environment, with the explicit ones taking precedence.
+    pub(crate) async fn new_with_overrides(src: &SettingsSource, overrides: Overrides) -> Result<Self> {
         // Try from file
         if let Some(file) = &src.settings_file {
             debug!(?file, "getting landscape settings from file");
-            return LandscapeSettings::new_from_file(file);
+            return LandscapeSettings::new_from_file(file, overrides).await;
         };
 
         // Try from url
         if let Some(url) = &src.settings_url {
             debug!(?url, "getting landscape settings from url");
-            return LandscapeSettings::new_from_url(url).await;
+            return LandscapeSettings::new_from_url(url, overrides).await;
         };
 
         Err(format_err!("settings file or url not provided"))
     }
 
     /// Create a new landscape settings instance from the file provided.
-    fn new_from_file(file: &Path) -> Result<Self> {
+    async fn new_from_file(file: &Path, overrides: Overrides) -> Result<Self> {
         let raw_data = fs::read_to_string(file)?;
-        let settings: LandscapeSettings = serde_yaml::from_str(&raw_data)?;
+        let settings = RawLandscapeSettings::parse(&raw_data)?;
+        let mut settings = settings.resolve_extends(file.parent(), &mut HashSet::new()).await?;
+        settings.apply_overrides(&overrides.merge(Overrides::from_env()));
         settings.validate().context("the landscape settings file provided is not valid")?;
 
         Ok(settings)
     }
 
     /// Create a new landscape settings instance from the url provided.
-    async fn new_from_url(url: &str) -> Result<Self> {
+    async fn new_from_url(url: &str, overrides: Overrides) -> Result<Self> {
         let resp = reqwest::get(url).await?;
         if resp.status() != StatusCode::OK {
             return Err(format_err!(
@@ -83,17 +115,223 @@ impl LandscapeSettings {
             ));
         }
         let raw_data = resp.text().await?;
-        let settings: LandscapeSettings = serde_yaml::from_str(&raw_data)?;
+        let settings = RawLandscapeSettings::parse(&raw_data)?;
+        let mut settings = settings.resolve_extends(None, &mut HashSet::new()).await?;
+        settings.apply_overrides(&overrides.merge(Overrides::from_env()));
         settings.validate().context("the landscape settings file provided is not valid")?;
 
         Ok(settings)
     }
 
+    /// Apply the overrides provided on top of this settings instance. Values
+    /// left unset in `overrides` leave the corresponding field untouched.
+    fn apply_overrides(&mut self, overrides: &Overrides) {
+        if let Some(foundation) = &overrides.foundation {
+            self.foundation = Some(foundation.clone());
+        }
+        if let Some(header_logo) = &overrides.images_header_logo {
+            self.images.header_logo = Some(header_logo.clone());
+        }
+        if let Some(color1) = &overrides.colors_color1 {
+            match self.colors.get_or_insert_with(Colors::default) {
+                Colors::Single(palette) => palette.color1 = Some(color1.clone()),
+                Colors::Themes(themes) => {
+                    themes.entry("default".to_string()).or_default().color1 = Some(color1.clone());
+                }
+            }
+        }
+        if let Some(members_category) = &overrides.members_category {
+            self.members_category = Some(members_category.clone());
+        }
+    }
+
+    /// Resolve the `extends` chain (if any), deep merging this settings
+    /// instance on top of its base, which is itself resolved recursively.
+    /// `base_dir` is used to resolve relative base file paths, and `visited`
+    /// keeps track of the bases already loaded so that cycles are detected.
+    fn resolve_extends<'a>(
+        self,
+        base_dir: Option<&'a Path>,
+        visited: &'a mut HashSet<String>,
+    ) -> Pin<Box<dyn Future<Output = Result<Self>> + Send + 'a>> {
+        Box::pin(async move {
+            let extends = match self.extends.clone() {
+                Some(extends) => extends,
+                None => return Ok(self),
+            };
+
+            let (raw_data, next_base_dir) = if extends.starts_with("http://") || extends.starts_with("https://") {
+                // Urls are already an unambiguous identifier, used as is.
+                if !visited.insert(extends.clone()) {
+                    return Err(format_err!("extends cycle detected at {extends}"));
+                }
+
+                let resp = reqwest::get(extends.as_str()).await?;
+                if resp.status() != StatusCode::OK {
+                    return Err(format_err!(
+                        "unexpected status code getting base settings file: {}",
+                        resp.status()
+                    ));
+                }
+                (resp.text().await?, None)
+            } else {
+                // Canonicalize the path before recording it as visited, so two
+                // different relative spellings of the same base file (e.g.
+                // `base.yml` vs `../dir/base.yml`) are recognized as the same
+                // node and a cycle through them is still detected.
+                let path = base_dir.map_or_else(|| PathBuf::from(&extends), |dir| dir.join(&extends));
+                let path = fs::canonicalize(&path)
+                    .with_context(|| format!("error reading base settings file {}", path.display()))?;
+
+                if !visited.insert(path.display().to_string()) {
+                    return Err(format_err!("extends cycle detected at {}", path.display()));
+                }
+
+                let raw_data = fs::read_to_string(&path)
+                    .with_context(|| format!("error reading base settings file {}", path.display()))?;
+                (raw_data, path.parent().map(Path::to_path_buf))
+            };
+
+            let base = RawLandscapeSettings::parse(&raw_data)?;
+            let base = base.resolve_extends(next_base_dir.as_deref(), visited).await?;
+
+            Ok(LandscapeSettings::merge(base, self))
+        })
+    }
+
+    /// Deep merge `overlay` on top of `base`, with values set in `overlay`
+    /// taking precedence over the ones inherited from `base`.
+    fn merge(base: Self, overlay: Self) -> Self {
+        Self {
+            foundation: overlay.foundation.or(base.foundation),
+            images: Images {
+                favicon: overlay.images.favicon.or(base.images.favicon),
+                footer_logo: overlay.images.footer_logo.or(base.images.footer_logo),
+                header_logo: overlay.images.header_logo.or(base.images.header_logo),
+                open_graph: overlay.images.open_graph.or(base.images.open_graph),
+            },
+            categories: overlay.categories.or(base.categories),
+            colors: Self::merge_colors(base.colors, overlay.colors),
+            extends: None,
+            extra: {
+                let mut extra = base.extra;
+                extra.extend(overlay.extra);
+                extra
+            },
+            featured_items: overlay.featured_items.or(base.featured_items),
+            grid_items_size: overlay.grid_items_size.or(base.grid_items_size),
+            groups: overlay.groups.or(base.groups),
+            members_category: overlay.members_category.or(base.members_category),
+            social_networks: match (base.social_networks, overlay.social_networks) {
+                (Some(base), Some(overlay)) => Some(SocialNetworks {
+                    facebook: overlay.facebook.or(base.facebook),
+                    flickr: overlay.flickr.or(base.flickr),
+                    github: overlay.github.or(base.github),
+                    instagram: overlay.instagram.or(base.instagram),
+                    linkedin: overlay.linkedin.or(base.linkedin),
+                    slack: overlay.slack.or(base.slack),
+                    twitch: overlay.twitch.or(base.twitch),
+                    twitter: overlay.twitter.or(base.twitter),
+                    wechat: overlay.wechat.or(base.wechat),
+                    youtube: overlay.youtube.or(base.youtube),
+                }),
+                (base, overlay) => overlay.or(base),
+            },
+        }
+    }
+
+    /// Deep merge `overlay` on top of `base`, theme by theme: a theme only
+    /// present in one of the two is kept as is, and a theme present in both
+    /// is merged field by field so a child can override e.g. just `color1`
+    /// of the `dark` theme without dropping the rest.
+    fn merge_colors(base: Option<Colors>, overlay: Option<Colors>) -> Option<Colors> {
+        let (base, overlay) = match (base, overlay) {
+            (Some(base), Some(overlay)) => (base, overlay),
+            (base, overlay) => return overlay.or(base),
+        };
+
+        let mut themes = base.into_themes();
+        for (name, overlay_palette) in overlay.into_themes() {
+            let merged = match themes.remove(&name) {
+                Some(base_palette) => Self::merge_palette(base_palette, overlay_palette),
+                None => overlay_palette,
+            };
+            themes.insert(name, merged);
+        }
+
+        Some(Colors::from_themes(themes))
+    }
+
+    /// Merge a single theme's palette field by field, with `overlay`'s
+    /// colors taking precedence over `base`'s.
+    fn merge_palette(base: ColorsPalette, overlay: ColorsPalette) -> ColorsPalette {
+        ColorsPalette {
+            color1: overlay.color1.or(base.color1),
+            color2: overlay.color2.or(base.color2),
+            color3: overlay.color3.or(base.color3),
+            color4: overlay.color4.or(base.color4),
+            color5: overlay.color5.or(base.color5),
+            color6: overlay.color6.or(base.color6),
+        }
+    }
+
+    /// Get the value at the dotted path provided (e.g. `ui.sidebar.collapsed`)
+    /// from the extra settings table, if present.
+    pub(crate) fn get(&self, path: &str) -> Option<&serde_yaml::Value> {
+        let mut segments = path.split('.');
+        let mut value = self.extra.get(segments.next()?)?;
+        for segment in segments {
+            value = value.as_mapping()?.get(&serde_yaml::Value::String(segment.to_string()))?;
+        }
+        Some(value)
+    }
+
+    /// Get the value at the dotted path provided from the extra settings
+    /// table, deserialized into `T`.
+    pub(crate) fn get_deserialized_opt<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<Option<T>> {
+        match self.get(path) {
+            Some(value) => Ok(Some(serde_yaml::from_value(value.clone())?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Set the value at the dotted path provided (e.g. `ui.sidebar.collapsed`)
+    /// in the extra settings table, creating any intermediate tables needed.
+    pub(crate) fn set(&mut self, path: &str, value: serde_yaml::Value) {
+        assert!(!path.is_empty(), "path cannot be empty");
+        let segments: Vec<&str> = path.split('.').collect();
+
+        if segments.len() == 1 {
+            self.extra.insert(segments[0].to_string(), value);
+            return;
+        }
+
+        if !matches!(self.extra.get(segments[0]), Some(serde_yaml::Value::Mapping(_))) {
+            self.extra
+                .insert(segments[0].to_string(), serde_yaml::Value::Mapping(serde_yaml::Mapping::new()));
+        }
+        let mut current = self.extra.get_mut(segments[0]).expect("key was just inserted");
+
+        for segment in &segments[1..segments.len() - 1] {
+            let key = serde_yaml::Value::String((*segment).to_string());
+            let mapping = current.as_mapping_mut().expect("intermediate value is not a table");
+            if !matches!(mapping.get(&key), Some(serde_yaml::Value::Mapping(_))) {
+                mapping.insert(key.clone(), serde_yaml::Value::Mapping(serde_yaml::Mapping::new()));
+            }
+            current = mapping.get_mut(&key).expect("key was just inserted");
+        }
+
+        let mapping = current.as_mapping_mut().expect("intermediate value is not a table");
+        let last_key = serde_yaml::Value::String(segments[segments.len() - 1].to_string());
+        mapping.insert(last_key, value);
+    }
+
     /// Validate landscape settings.
     fn validate(&self) -> Result<()> {
-        // Check foundation is not empty
-        if self.foundation.is_empty() {
-            return Err(format_err!("foundation cannot be empty"));
+        // Check foundation is set and not empty
+        match &self.foundation {
+            Some(foundation) if !foundation.is_empty() => {}
+            _ => return Err(format_err!("foundation cannot be empty")),
         }
 
         // Check members category is not empty
@@ -143,20 +381,38 @@ impl LandscapeSettings {
     /// Check colors format
     fn validate_colors(&self) -> Result<()> {
         if let Some(colors) = &self.colors {
-            let colors = [
-                ("color1", &colors.color1),
-                ("color2", &colors.color2),
-                ("color3", &colors.color3),
-                ("color4", &colors.color4),
-                ("color5", &colors.color5),
-                ("color6", &colors.color6),
-            ];
-
-            for (name, value) in colors {
-                if !RGBA.is_match(value) {
-                    return Err(format_err!(
-                        r#"{name} is not valid (format: "rgba(0, 107, 204, 1)")"#
-                    ));
+            let themes: Vec<(&str, &ColorsPalette)> = match colors {
+                Colors::Single(palette) => vec![("default", palette)],
+                Colors::Themes(themes) => {
+                    if !themes.contains_key("default") {
+                        return Err(format_err!("colors: the default theme is required"));
+                    }
+                    themes.iter().map(|(name, palette)| (name.as_str(), palette)).collect()
+                }
+            };
+
+            for (theme, palette) in themes {
+                let fields = [
+                    ("color1", &palette.color1),
+                    ("color2", &palette.color2),
+                    ("color3", &palette.color3),
+                    ("color4", &palette.color4),
+                    ("color5", &palette.color5),
+                    ("color6", &palette.color6),
+                ];
+
+                for (name, value) in fields {
+                    match value {
+                        Some(value) if RGBA.is_match(value) => {}
+                        Some(_) => {
+                            return Err(format_err!(
+                                r#"{name} in theme [{theme}] is not valid (format: "rgba(0, 107, 204, 1)")"#
+                            ))
+                        }
+                        None => {
+                            return Err(format_err!("{name} in theme [{theme}] cannot be empty"))
+                        }
+                    }
                 }
             }
         }
@@ -234,6 +490,217 @@ impl LandscapeSettings {
     }
 }
 
+/// Settings overrides, applied on top of whatever is defined in the settings
+/// file right before validation. This allows CI pipelines to tweak a single
+/// value (e.g. the header logo or the foundation name) without having to
+/// template the whole settings file. Unset fields leave the corresponding
+/// setting untouched.
+///
+/// Overrides can come from the environment (see `from_env`), which is what
+/// `LandscapeSettings::new` and `new_from_file`/`new_from_url` apply today.
+/// The setter methods below build an `Overrides` instance explicitly instead,
+/// so that a caller with its own CLI flags (e.g. `SettingsSource`, defined
+/// outside this module) can pass one to `new_with_overrides` and have it take
+/// precedence over the environment; no such caller exists in this module yet.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Overrides {
+    pub foundation: Option<String>,
+    pub images_header_logo: Option<String>,
+    pub colors_color1: Option<String>,
+    pub members_category: Option<String>,
+}
+
+impl Overrides {
+    /// Create a new overrides instance from the corresponding environment
+    /// variables, falling back to unset for the ones not provided.
+    fn from_env() -> Self {
+        Self {
+            foundation: env::var("LANDSCAPE_FOUNDATION").ok(),
+            images_header_logo: env::var("LANDSCAPE_IMAGES_HEADER_LOGO").ok(),
+            colors_color1: env::var("LANDSCAPE_COLORS_COLOR1").ok(),
+            members_category: env::var("LANDSCAPE_MEMBERS_CATEGORY").ok(),
+        }
+    }
+
+    /// Set the foundation override explicitly (e.g. from a CLI flag).
+    pub(crate) fn set_foundation(mut self, foundation: String) -> Self {
+        self.foundation = Some(foundation);
+        self
+    }
+
+    /// Set the header logo override explicitly (e.g. from a CLI flag).
+    pub(crate) fn set_images_header_logo(mut self, header_logo: String) -> Self {
+        self.images_header_logo = Some(header_logo);
+        self
+    }
+
+    /// Set the first color override explicitly (e.g. from a CLI flag).
+    pub(crate) fn set_colors_color1(mut self, color1: String) -> Self {
+        self.colors_color1 = Some(color1);
+        self
+    }
+
+    /// Set the members category override explicitly (e.g. from a CLI flag).
+    pub(crate) fn set_members_category(mut self, members_category: String) -> Self {
+        self.members_category = Some(members_category);
+        self
+    }
+
+    /// Merge `self` with `other`, with `self`'s values taking precedence.
+    /// Used to let explicit overrides win over the ones read from the
+    /// environment.
+    fn merge(self, other: Self) -> Self {
+        Self {
+            foundation: self.foundation.or(other.foundation),
+            images_header_logo: self.images_header_logo.or(other.images_header_logo),
+            colors_color1: self.colors_color1.or(other.colors_color1),
+            members_category: self.members_category.or(other.members_category),
+        }
+    }
+}
+
+/// Landscape settings file, in either the current or the legacy format. This
+/// type lets us detect which format a settings file uses and, when it's the
+/// legacy one, convert it to the current `LandscapeSettings` transparently so
+/// that the rest of the code only ever has to deal with the latter.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum RawLandscapeSettings {
+    Legacy(LegacySettings),
+    Current(LandscapeSettings),
+}
+
+impl RawLandscapeSettings {
+    /// Parse the raw settings data provided, converting it to the current
+    /// format when it uses the legacy one.
+    fn parse(raw_data: &str) -> Result<LandscapeSettings> {
+        match serde_yaml::from_str(raw_data)? {
+            RawLandscapeSettings::Legacy(legacy) => {
+                debug!("legacy settings file format detected, converting it");
+                Ok(legacy.into())
+            }
+            RawLandscapeSettings::Current(settings) => Ok(settings),
+        }
+    }
+}
+
+/// Legacy landscape settings. This is the format used by landscapes that
+/// haven't migrated to the new settings file format yet. It is converted to
+/// `LandscapeSettings` via the `From` implementation below.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct LegacySettings {
+    pub foundation_name: String,
+    pub images: Images,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub big_picture: Option<LegacyBigPicture>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub colors: Option<ColorsPalette>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlights: Option<Vec<LegacyHighlightRule>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub members_category: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub social_media: Option<SocialNetworks>,
+}
+
+/// Legacy big picture information, containing the categories and groups used
+/// to organize items in the old settings file format.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct LegacyBigPicture {
+    #[serde(default)]
+    pub categories: Vec<LegacyCategory>,
+
+    #[serde(default)]
+    pub groups: Vec<LegacyGroup>,
+}
+
+/// Legacy category information.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct LegacyCategory {
+    pub category_name: String,
+
+    #[serde(default)]
+    pub subcategories: Vec<String>,
+}
+
+/// Legacy group information.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct LegacyGroup {
+    pub group_name: String,
+
+    #[serde(default)]
+    pub categories: Vec<String>,
+}
+
+/// Legacy highlight rule, the predecessor of `FeaturedItemRule`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct LegacyHighlightRule {
+    pub key: String,
+    pub values: Vec<String>,
+}
+
+impl From<LegacySettings> for LandscapeSettings {
+    fn from(legacy: LegacySettings) -> Self {
+        let (categories, groups) = match legacy.big_picture {
+            Some(big_picture) => {
+                let categories = big_picture
+                    .categories
+                    .into_iter()
+                    .map(|c| Category {
+                        name: c.category_name,
+                        subcategories: c.subcategories,
+                    })
+                    .collect();
+                let groups = big_picture
+                    .groups
+                    .into_iter()
+                    .map(|g| Group {
+                        name: g.group_name,
+                        categories: g.categories,
+                    })
+                    .collect();
+                (Some(categories), Some(groups))
+            }
+            None => (None, None),
+        };
+
+        let featured_items = legacy.highlights.map(|highlights| {
+            highlights
+                .into_iter()
+                .map(|h| FeaturedItemRule {
+                    field: h.key,
+                    options: h
+                        .values
+                        .into_iter()
+                        .map(|value| FeaturedItemRuleOption {
+                            value,
+                            label: None,
+                            order: None,
+                        })
+                        .collect(),
+                })
+                .collect()
+        });
+
+        LandscapeSettings {
+            foundation: Some(legacy.foundation_name),
+            images: legacy.images,
+            categories,
+            colors: legacy.colors.map(Colors::Single),
+            featured_items,
+            groups,
+            members_category: legacy.members_category,
+            social_networks: legacy.social_media,
+            ..Default::default()
+        }
+    }
+}
+
 lazy_static! {
     /// RGBA regular expression.
     pub(crate) static ref RGBA: Regex =
@@ -241,15 +708,69 @@ lazy_static! {
             .expect("exprs in RGBA to be valid");
 }
 
-/// Colors used across the landscape UI.
+/// Colors used across the landscape UI. Landscapes can either provide a
+/// single palette, used for all themes, or a map of named themes (e.g.
+/// `default` and `dark`) so that the web application can switch between them
+/// client side. A `default` theme is always required.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum Colors {
+    Themes(HashMap<String, ColorsPalette>),
+    Single(ColorsPalette),
+}
+
+impl Default for Colors {
+    fn default() -> Self {
+        Colors::Single(ColorsPalette::default())
+    }
+}
+
+impl Colors {
+    /// Return this instance as a map of named themes, so that callers don't
+    /// need to special case the single-palette form (which is equivalent to
+    /// a themes map with only a `default` entry).
+    fn into_themes(self) -> HashMap<String, ColorsPalette> {
+        match self {
+            Colors::Single(palette) => HashMap::from([("default".to_string(), palette)]),
+            Colors::Themes(themes) => themes,
+        }
+    }
+
+    /// Build a `Colors` instance from a map of named themes, collapsing it
+    /// back to the single-palette form when it only contains `default`.
+    fn from_themes(mut themes: HashMap<String, ColorsPalette>) -> Self {
+        if themes.len() == 1 {
+            if let Some(palette) = themes.remove("default") {
+                return Colors::Single(palette);
+            }
+        }
+
+        Colors::Themes(themes)
+    }
+}
+
+/// Set of colors that make up a single theme's palette. Each color is
+/// optional so that a child file using `extends` can override just one of
+/// them and inherit the rest from the base file.
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
-pub(crate) struct Colors {
-    pub color1: String,
-    pub color2: String,
-    pub color3: String,
-    pub color4: String,
-    pub color5: String,
-    pub color6: String,
+pub(crate) struct ColorsPalette {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color1: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color2: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color3: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color4: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color5: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color6: Option<String>,
 }
 
 /// Featured item rule information. A featured item is specially highlighted in
@@ -339,3 +860,175 @@ pub(crate) struct SocialNetworks {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub youtube: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Return a path in the system's temp directory that is unique to this
+    /// test run, so tests running concurrently don't clash.
+    fn temp_file_path(name: &str) -> PathBuf {
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+        let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir().join(format!("landscape2-settings-test-{}-{n}-{name}", std::process::id()))
+    }
+
+    fn minimal_settings_yaml() -> &'static str {
+        "foundation: cncf\nimages:\n  header_logo: https://example.com/logo.svg\n"
+    }
+
+    #[tokio::test]
+    async fn extends_inherits_foundation_when_child_omits_it() {
+        let base_path = temp_file_path("base.yml");
+        fs::write(&base_path, minimal_settings_yaml()).unwrap();
+
+        let child_path = temp_file_path("child.yml");
+        fs::write(
+            &child_path,
+            format!("extends: {}\nimages:\n  footer_logo: https://example.com/footer.svg\n", base_path.display()),
+        )
+        .unwrap();
+
+        let settings = LandscapeSettings::new_from_file(&child_path, Overrides::default()).await.unwrap();
+
+        assert_eq!(settings.foundation.as_deref(), Some("cncf"));
+        assert_eq!(settings.images.header_logo.as_deref(), Some("https://example.com/logo.svg"));
+        assert_eq!(settings.images.footer_logo.as_deref(), Some("https://example.com/footer.svg"));
+
+        fs::remove_file(&base_path).unwrap();
+        fs::remove_file(&child_path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn extends_cycle_is_detected_across_relative_spellings() {
+        let dir = std::env::temp_dir().join(format!("landscape2-settings-test-cycle-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let a = dir.join("a.yml");
+        let b = dir.join("b.yml");
+
+        fs::write(&a, format!("{}extends: b.yml\n", minimal_settings_yaml())).unwrap();
+        fs::write(&b, format!("{}extends: ./a.yml\n", minimal_settings_yaml())).unwrap();
+
+        let err = LandscapeSettings::new_from_file(&a, Overrides::default()).await.unwrap_err();
+        assert!(err.to_string().contains("cycle"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn merge_colors_keeps_base_theme_untouched_by_overlay() {
+        let base = Colors::Themes(HashMap::from([
+            (
+                "default".to_string(),
+                ColorsPalette {
+                    color1: Some("rgba(0, 0, 0, 1)".to_string()),
+                    color2: Some("rgba(0, 0, 0, 1)".to_string()),
+                    color3: Some("rgba(0, 0, 0, 1)".to_string()),
+                    color4: Some("rgba(0, 0, 0, 1)".to_string()),
+                    color5: Some("rgba(0, 0, 0, 1)".to_string()),
+                    color6: Some("rgba(0, 0, 0, 1)".to_string()),
+                },
+            ),
+            ("dark".to_string(), ColorsPalette::default()),
+        ]));
+        let overlay = Colors::Themes(HashMap::from([(
+            "dark".to_string(),
+            ColorsPalette {
+                color1: Some("rgba(1, 1, 1, 1)".to_string()),
+                ..Default::default()
+            },
+        )]));
+
+        let merged = LandscapeSettings::merge_colors(Some(base), Some(overlay)).unwrap().into_themes();
+
+        assert!(merged.contains_key("default"), "base-only theme must survive the merge");
+        assert_eq!(merged["dark"].color1.as_deref(), Some("rgba(1, 1, 1, 1)"));
+    }
+
+    #[test]
+    fn get_and_set_walk_dotted_paths() {
+        let mut settings = LandscapeSettings::default();
+        settings.set("ui.sidebar.collapsed", serde_yaml::Value::Bool(true));
+
+        assert_eq!(settings.get("ui.sidebar.collapsed"), Some(&serde_yaml::Value::Bool(true)));
+        assert_eq!(settings.get("ui.sidebar.missing"), None);
+        assert_eq!(
+            settings.get_deserialized_opt::<bool>("ui.sidebar.collapsed").unwrap(),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn extra_settings_are_parsed_from_yaml() {
+        let settings = RawLandscapeSettings::parse(
+            r#"
+foundation: cncf
+images:
+  header_logo: https://example.com/logo.svg
+ui:
+  sidebar:
+    collapsed: true
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            settings.get_deserialized_opt::<bool>("ui.sidebar.collapsed").unwrap(),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn themed_colors_yaml_is_parsed_as_themes_not_single() {
+        let settings = RawLandscapeSettings::parse(
+            r#"
+foundation: cncf
+images:
+  header_logo: https://example.com/logo.svg
+colors:
+  default:
+    color1: "rgba(0, 107, 204, 1)"
+    color2: "rgba(0, 85, 163, 1)"
+  dark:
+    color1: "rgba(255, 255, 255, 1)"
+"#,
+        )
+        .unwrap();
+
+        let themes = match settings.colors.unwrap() {
+            Colors::Themes(themes) => themes,
+            Colors::Single(_) => panic!("a map of named themes must not be parsed as a single palette"),
+        };
+
+        assert_eq!(themes["default"].color1.as_deref(), Some("rgba(0, 107, 204, 1)"));
+        assert_eq!(themes["dark"].color1.as_deref(), Some("rgba(255, 255, 255, 1)"));
+    }
+
+    #[test]
+    fn legacy_settings_file_is_detected_and_converted() {
+        let settings = RawLandscapeSettings::parse(
+            r#"
+foundation_name: cncf
+images:
+  header_logo: https://example.com/logo.svg
+big_picture:
+  categories:
+    - category_name: App Definition
+      subcategories: [Database]
+  groups:
+    - group_name: Platform
+      categories: [App Definition]
+highlights:
+  - key: annual_review_date_2024
+    values: ["2024"]
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(settings.foundation.as_deref(), Some("cncf"));
+        assert_eq!(settings.categories.as_ref().unwrap()[0].name, "App Definition");
+        assert_eq!(settings.groups.as_ref().unwrap()[0].name, "Platform");
+        assert_eq!(settings.featured_items.as_ref().unwrap()[0].field, "annual_review_date_2024");
+    }
+}